@@ -0,0 +1,53 @@
+// Persists the user's online/offline preference and last TMDB query in
+// config.json, alongside movies.json.
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::MovieError;
+
+/// Whether TMDB enrichment is allowed to reach the network.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum AppMode {
+    #[default]
+    Online,
+    Offline,
+}
+
+impl std::fmt::Display for AppMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AppMode::Online => write!(f, "online"),
+            AppMode::Offline => write!(f, "offline"),
+        }
+    }
+}
+
+/// Small persisted settings that should survive a restart.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AppConfig {
+    #[serde(default)]
+    pub mode: AppMode,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_tmdb_query: Option<String>,
+}
+
+impl AppConfig {
+    /// Loads the config from `path`, or returns the defaults (online mode,
+    /// no last query) if the file doesn't exist yet.
+    pub fn load(path: &Path) -> Result<Self, MovieError> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let data = std::fs::read_to_string(path)?;
+        serde_json::from_str(&data)
+            .map_err(|e| MovieError::InvalidInput(format!("corrupt config in {}: {}", path.display(), e)))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), MovieError> {
+        let json_data = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, json_data)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,34 @@
+// Error type for the movie database's I/O and serialization failures.
+
+/// Errors that can occur while loading, saving, or running the movie
+/// database, in place of the previous `eprintln!`-and-swallow handling.
+#[derive(Debug)]
+pub enum MovieError {
+    Io(std::io::Error),
+    Serde(serde_json::Error),
+    InvalidInput(String),
+}
+
+impl std::fmt::Display for MovieError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MovieError::Io(e) => write!(f, "I/O error: {}", e),
+            MovieError::Serde(e) => write!(f, "failed to serialize movie data: {}", e),
+            MovieError::InvalidInput(msg) => write!(f, "invalid input: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for MovieError {}
+
+impl From<std::io::Error> for MovieError {
+    fn from(e: std::io::Error) -> Self {
+        MovieError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for MovieError {
+    fn from(e: serde_json::Error) -> Self {
+        MovieError::Serde(e)
+    }
+}
@@ -1,5 +1,11 @@
 // Entry point for the Movie Database Management System application.
 
+mod config;
+mod errors;
+mod metadata;
+mod paths;
+mod poster;
+mod query;
 mod utils;
 
 fn main() {
@@ -25,7 +31,7 @@ fn main() {
             eprintln!("Program ended with error code: {}", code);
             std::process::exit(code);
         }
-        // The main logic function returned an error (as a String)
+        // The main logic function returned an error (a MovieError)
         Ok(Err(e)) => {
             eprintln!("Error: {}", e);
             std::process::exit(1);
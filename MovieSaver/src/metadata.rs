@@ -0,0 +1,194 @@
+// TMDB metadata enrichment client.
+// Looks movies up on themoviedb.org so the user doesn't have to type in
+// overview, genres, and runtime by hand.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::MovieError;
+
+const TMDB_API_KEY_VAR: &str = "TMDB_API_KEY";
+const TMDB_SEARCH_URL: &str = "https://api.themoviedb.org/3/search/movie";
+const TMDB_MOVIE_URL: &str = "https://api.themoviedb.org/3/movie";
+
+/// A single candidate match returned by TMDB's search endpoint.
+#[derive(Debug, Clone, Deserialize)]
+pub struct TmdbCandidate {
+    pub id: i64,
+    pub title: String,
+    #[serde(default)]
+    pub overview: String,
+    #[serde(default)]
+    pub release_date: String,
+    #[serde(default)]
+    pub poster_path: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TmdbSearchResponse {
+    #[serde(default)]
+    results: Vec<TmdbCandidate>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TmdbGenre {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct TmdbMovieDetails {
+    #[serde(default)]
+    genres: Vec<TmdbGenre>,
+    runtime: Option<i32>,
+}
+
+/// Details merged back into a `MovieInfo` once the user picks a candidate.
+/// Also what gets persisted in `metadata_cache.json` so offline lookups can
+/// reuse a previous online search.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnrichedDetails {
+    pub title: String,
+    pub overview: String,
+    pub genres: Vec<String>,
+    pub runtime: Option<i32>,
+    pub tmdb_id: i64,
+    pub poster_path: Option<String>,
+}
+
+/// Errors that can occur while talking to TMDB.
+#[derive(Debug)]
+pub enum MetadataError {
+    MissingApiKey,
+    Request(reqwest::Error),
+    NoResults,
+}
+
+impl std::fmt::Display for MetadataError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MetadataError::MissingApiKey => {
+                write!(f, "{} is not set; can't reach TMDB", TMDB_API_KEY_VAR)
+            }
+            MetadataError::Request(e) => write!(f, "TMDB request failed: {}", e),
+            MetadataError::NoResults => write!(f, "TMDB returned no matches"),
+        }
+    }
+}
+
+impl std::error::Error for MetadataError {}
+
+impl From<reqwest::Error> for MetadataError {
+    fn from(e: reqwest::Error) -> Self {
+        MetadataError::Request(e)
+    }
+}
+
+/// A small async client for TMDB's search and movie-details endpoints.
+pub struct TmdbClient {
+    api_key: String,
+    http: reqwest::Client,
+}
+
+impl TmdbClient {
+    /// Builds a client using the API key from the `TMDB_API_KEY` environment
+    /// variable.
+    pub fn from_env() -> Result<Self, MetadataError> {
+        let api_key = std::env::var(TMDB_API_KEY_VAR).map_err(|_| MetadataError::MissingApiKey)?;
+        Ok(Self {
+            api_key,
+            http: reqwest::Client::new(),
+        })
+    }
+
+    /// Searches for `title`/`year` and returns the matches TMDB finds,
+    /// closest match first.
+    pub async fn search(&self, title: &str, year: i32) -> Result<Vec<TmdbCandidate>, MetadataError> {
+        let year_str = year.to_string();
+        let query = [
+            ("api_key", self.api_key.as_str()),
+            ("query", title),
+            ("year", year_str.as_str()),
+        ];
+        let response = self.http.get(TMDB_SEARCH_URL).query(&query).send().await?;
+        let parsed: TmdbSearchResponse = response.error_for_status()?.json().await?;
+        if parsed.results.is_empty() {
+            return Err(MetadataError::NoResults);
+        }
+        Ok(parsed.results)
+    }
+
+    /// Fetches genres and runtime for a candidate and merges them with the
+    /// fields already present on the search result.
+    pub async fn fetch_details(&self, candidate: &TmdbCandidate) -> Result<EnrichedDetails, MetadataError> {
+        let url = format!("{}/{}", TMDB_MOVIE_URL, candidate.id);
+        let response = self
+            .http
+            .get(&url)
+            .query(&[("api_key", self.api_key.as_str())])
+            .send()
+            .await?;
+        let details: TmdbMovieDetails = response.error_for_status()?.json().await?;
+        Ok(EnrichedDetails {
+            title: candidate.title.clone(),
+            overview: candidate.overview.clone(),
+            genres: details.genres.into_iter().map(|g| g.name).collect(),
+            runtime: details.runtime,
+            tmdb_id: candidate.id,
+            poster_path: candidate.poster_path.clone(),
+        })
+    }
+}
+
+/// A cache of previously fetched TMDB details, persisted to
+/// `metadata_cache.json` so enrichment survives restarts without network
+/// access (see `AppMode::Offline`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MetadataCache {
+    entries: HashMap<i64, EnrichedDetails>,
+}
+
+impl MetadataCache {
+    /// Loads the cache from `path`, or an empty cache if it doesn't exist yet.
+    pub fn load(path: &Path) -> Result<Self, MovieError> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let data = std::fs::read_to_string(path)?;
+        serde_json::from_str(&data).map_err(|e| {
+            MovieError::InvalidInput(format!("corrupt metadata cache in {}: {}", path.display(), e))
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), MovieError> {
+        let json_data = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, json_data)?;
+        Ok(())
+    }
+
+    pub fn insert(&mut self, details: EnrichedDetails) {
+        self.entries.insert(details.tmdb_id, details);
+    }
+
+    /// Looks for a cached entry matching `title` (case-insensitive).
+    /// Prefers an exact title match; if several entries merely contain
+    /// `title` as a substring, picks deterministically by sorting on
+    /// `(title, tmdb_id)` rather than relying on `HashMap` iteration order.
+    pub fn find_by_title(&self, title: &str) -> Option<&EnrichedDetails> {
+        let needle = title.to_lowercase();
+
+        if let Some(exact) = self
+            .entries
+            .values()
+            .find(|details| details.title.to_lowercase() == needle)
+        {
+            return Some(exact);
+        }
+
+        self.entries
+            .values()
+            .filter(|details| details.title.to_lowercase().contains(&needle))
+            .min_by_key(|details| (details.title.to_lowercase(), details.tmdb_id))
+    }
+}
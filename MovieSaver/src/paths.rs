@@ -0,0 +1,46 @@
+// Resolves where MovieSaver stores its data, following OS conventions
+// (e.g. ~/.local/share/... on Linux, %APPDATA% on Windows) instead of a
+// "MovieData" folder relative to the current working directory.
+
+use std::fs;
+use std::path::PathBuf;
+
+use directories::ProjectDirs;
+
+use crate::errors::MovieError;
+
+const FALLBACK_DIR: &str = "MovieData";
+
+/// Returns the path to `movies.json` inside the platform data directory,
+/// creating the directory first if it doesn't exist yet.
+///
+/// Falls back to a `MovieData` folder in the current directory if the
+/// platform data directory can't be determined.
+pub fn movie_data_file() -> Result<PathBuf, MovieError> {
+    let dir = data_dir()?;
+    fs::create_dir_all(&dir)?;
+    Ok(dir.join("movies.json"))
+}
+
+/// Returns the path to `config.json`, creating the data directory first.
+pub fn config_file() -> Result<PathBuf, MovieError> {
+    let dir = data_dir()?;
+    fs::create_dir_all(&dir)?;
+    Ok(dir.join("config.json"))
+}
+
+/// Returns the path to `metadata_cache.json`, creating the data directory
+/// first.
+pub fn metadata_cache_file() -> Result<PathBuf, MovieError> {
+    let dir = data_dir()?;
+    fs::create_dir_all(&dir)?;
+    Ok(dir.join("metadata_cache.json"))
+}
+
+/// Resolves the platform data directory for MovieSaver, without creating it.
+pub fn data_dir() -> Result<PathBuf, MovieError> {
+    match ProjectDirs::from("com", "DC-Code123", "MovieSaver-RS") {
+        Some(dirs) => Ok(dirs.data_dir().to_path_buf()),
+        None => Ok(PathBuf::from(FALLBACK_DIR)),
+    }
+}
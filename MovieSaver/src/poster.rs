@@ -0,0 +1,69 @@
+// Downloads a movie's TMDB poster into the data directory, with a progress
+// bar driven by the response's Content-Length header.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use futures_util::StreamExt;
+use indicatif::{ProgressBar, ProgressStyle};
+
+use crate::errors::MovieError;
+use crate::utils::MovieInfo;
+
+const TMDB_IMAGE_BASE: &str = "https://image.tmdb.org/t/p/w500";
+
+/// Downloads the poster for `movie` into `posters/<tmdb_id>.jpg` under
+/// `data_dir`, streaming the response body and reporting progress.
+///
+/// Returns `MovieError::InvalidInput` if `movie` has no `poster_path` or no
+/// `tmdb_id`.
+pub fn download_poster(movie: &MovieInfo, data_dir: &Path) -> Result<PathBuf, MovieError> {
+    let (Some(poster_path), Some(tmdb_id)) = (&movie.poster_path, movie.tmdb_id) else {
+        return Err(MovieError::InvalidInput(format!(
+            "\"{}\" has no TMDB poster to download",
+            movie.title
+        )));
+    };
+
+    let posters_dir = data_dir.join("posters");
+    std::fs::create_dir_all(&posters_dir)?;
+    let dest = posters_dir.join(format!("{}.jpg", tmdb_id));
+
+    let url = format!("{}{}", TMDB_IMAGE_BASE, poster_path);
+    let runtime = tokio::runtime::Runtime::new()?;
+    runtime.block_on(stream_to_file(&url, &dest))?;
+
+    Ok(dest)
+}
+
+async fn stream_to_file(url: &str, dest: &Path) -> Result<(), MovieError> {
+    let response = reqwest::get(url)
+        .await
+        .map_err(to_io_error)?
+        .error_for_status()
+        .map_err(to_io_error)?;
+    let total_size = response.content_length();
+
+    let progress = match total_size {
+        Some(size) => ProgressBar::new(size).with_style(
+            ProgressStyle::with_template("{bar:40.cyan/blue} {bytes}/{total_bytes} ({eta})")
+                .unwrap(),
+        ),
+        None => ProgressBar::new_spinner(),
+    };
+
+    let mut file = std::fs::File::create(dest)?;
+    let mut stream = response.bytes_stream();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(to_io_error)?;
+        file.write_all(&chunk)?;
+        progress.inc(chunk.len() as u64);
+    }
+    progress.finish_with_message("done");
+
+    Ok(())
+}
+
+fn to_io_error(e: reqwest::Error) -> MovieError {
+    MovieError::Io(std::io::Error::other(e))
+}
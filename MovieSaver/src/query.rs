@@ -0,0 +1,153 @@
+// Parses a small `key:value` query grammar and filters movies against it,
+// e.g. `title:matrix year:1999-2003 price:<15`.
+
+use crate::utils::MovieInfo;
+
+/// A year filter: either an exact year or an inclusive range.
+#[derive(Debug, Clone, PartialEq)]
+pub enum YearFilter {
+    Exact(i32),
+    Range(i32, i32),
+}
+
+/// A parsed search query. Every field is optional; an empty `Query` matches
+/// everything.
+#[derive(Debug, Clone, Default)]
+pub struct Query {
+    pub title: Option<String>,
+    pub year: Option<YearFilter>,
+    pub price_max: Option<f64>,
+}
+
+/// Parses a one-line query such as `title:matrix year:1999-2003 price:<15`
+/// into a `Query`. Unknown keys and malformed tokens are ignored so a typo
+/// in one field doesn't blow away the rest of the query.
+pub fn parse_query(input: &str) -> Query {
+    let mut query = Query::default();
+
+    for token in input.split_whitespace() {
+        let Some((key, value)) = token.split_once(':') else {
+            continue;
+        };
+        match key {
+            "title" => query.title = Some(value.to_string()),
+            "year" => query.year = parse_year_filter(value),
+            "price" => query.price_max = parse_price_ceiling(value),
+            _ => {}
+        }
+    }
+
+    query
+}
+
+fn parse_year_filter(value: &str) -> Option<YearFilter> {
+    if let Some((start, end)) = value.split_once('-') {
+        let start: i32 = start.parse().ok()?;
+        let end: i32 = end.parse().ok()?;
+        Some(YearFilter::Range(start, end))
+    } else {
+        value.parse().ok().map(YearFilter::Exact)
+    }
+}
+
+fn parse_price_ceiling(value: &str) -> Option<f64> {
+    value.strip_prefix('<').unwrap_or(value).parse().ok()
+}
+
+/// Returns the movies matching every field set on `query`.
+pub fn filter_movies<'a>(movies: &'a [MovieInfo], query: &Query) -> Vec<&'a MovieInfo> {
+    movies
+        .iter()
+        .filter(|movie| matches_title(movie, query))
+        .filter(|movie| matches_year(movie, query))
+        .filter(|movie| matches_price(movie, query))
+        .collect()
+}
+
+fn matches_title(movie: &MovieInfo, query: &Query) -> bool {
+    match &query.title {
+        Some(title) => movie.title.to_lowercase().contains(&title.to_lowercase()),
+        None => true,
+    }
+}
+
+fn matches_year(movie: &MovieInfo, query: &Query) -> bool {
+    match &query.year {
+        Some(YearFilter::Exact(year)) => movie.year == *year,
+        Some(YearFilter::Range(start, end)) => movie.year >= *start && movie.year <= *end,
+        None => true,
+    }
+}
+
+fn matches_price(movie: &MovieInfo, query: &Query) -> bool {
+    match query.price_max {
+        Some(max) => movie.price < max,
+        None => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn movie(title: &str, year: i32, price: f64) -> MovieInfo {
+        MovieInfo {
+            timestamp: "2024-01-01 00:00:00".to_string(),
+            title: title.to_string(),
+            year,
+            price,
+            overview: None,
+            genres: None,
+            runtime: None,
+            tmdb_id: None,
+            poster_path: None,
+        }
+    }
+
+    #[test]
+    fn parses_exact_year() {
+        let query = parse_query("year:1999");
+        assert_eq!(query.year, Some(YearFilter::Exact(1999)));
+    }
+
+    #[test]
+    fn parses_year_range() {
+        let query = parse_query("year:1999-2003");
+        assert_eq!(query.year, Some(YearFilter::Range(1999, 2003)));
+    }
+
+    #[test]
+    fn parses_price_ceiling() {
+        let query = parse_query("price:<15");
+        assert_eq!(query.price_max, Some(15.0));
+    }
+
+    #[test]
+    fn ignores_unknown_and_malformed_tokens() {
+        let query = parse_query("bogus year:notanumber wat title:matrix");
+        assert_eq!(query.year, None);
+        assert_eq!(query.title, Some("matrix".to_string()));
+    }
+
+    #[test]
+    fn filters_by_title_substring_case_insensitive() {
+        let movies = vec![movie("The Matrix", 1999, 9.99), movie("Inception", 2010, 12.0)];
+        let query = parse_query("title:MATRIX");
+        let results = filter_movies(&movies, &query);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].title, "The Matrix");
+    }
+
+    #[test]
+    fn filters_by_year_range_and_price_ceiling_combined() {
+        let movies = vec![
+            movie("The Matrix", 1999, 9.99),
+            movie("Matrix Reloaded", 2003, 20.0),
+            movie("Matrix Revolutions", 2003, 5.0),
+        ];
+        let query = parse_query("title:matrix year:1999-2003 price:<15");
+        let results = filter_movies(&movies, &query);
+        let titles: Vec<&str> = results.iter().map(|m| m.title.as_str()).collect();
+        assert_eq!(titles, vec!["The Matrix", "Matrix Revolutions"]);
+    }
+}
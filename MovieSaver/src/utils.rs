@@ -4,15 +4,44 @@ use std::path::Path;
 use chrono::{FixedOffset, Utc};
 use serde::{Deserialize, Serialize};
 
+use crate::config::{AppConfig, AppMode};
+use crate::errors::MovieError;
+use crate::metadata::{EnrichedDetails, MetadataCache, TmdbClient};
+
 /// Struct representing movie information.
 /// Each movie has a timestamp, title, release year, and price.
+/// The TMDB fields are `None` for movies entered by hand or saved before
+/// enrichment existed, so `skip_serializing_if` keeps old `movies.json`
+/// files loading without a migration.
 #[derive(Debug, Clone, Serialize, Deserialize)] // This allows the struct to obtain proprties of Debug and Clone and also to be serialized and deserialized.
-                                                // I.e we can print it, clone it, and convert it to/from formats like JSON if needed. 
+                                                // I.e we can print it, clone it, and convert it to/from formats like JSON if needed.
 pub struct MovieInfo {
     pub timestamp: String,
     pub title: String,
     pub year: i32,
     pub price: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub overview: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub genres: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub runtime: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tmdb_id: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub poster_path: Option<String>,
+}
+
+impl MovieInfo {
+    /// Merges TMDB details into this movie, keeping the manually entered
+    /// title/year/price untouched.
+    fn apply_enrichment(&mut self, details: EnrichedDetails) {
+        self.overview = Some(details.overview);
+        self.genres = Some(details.genres);
+        self.runtime = details.runtime;
+        self.tmdb_id = Some(details.tmdb_id);
+        self.poster_path = details.poster_path;
+    }
 }
 
 /// Returns the current timestamp as a formatted string in Central Africa Time (UTC+2).
@@ -26,29 +55,29 @@ pub fn get_current_timestamp() -> String {
     cat_time.format("%Y-%m-%d %H:%M:%S").to_string()
 } //TODO: Consider expanding the supported timezones in the future.
 
-/// Ensures the "MovieData" directory exists for storing movie files.
-/// If the directory does not exist, it will be created.
-/// Prints an error message if directory creation fails.
-pub fn ensure_movie_directory_exists() {
-    let dir = Path::new("MovieData");
-    if !dir.exists() {
-        if let Err(e) = fs::create_dir(dir) {
-            eprintln!("Error creating directory: {}", e);
-        }
-    }
-}
-
 /// Prints the details of a single movie to the console.
 pub fn display_movie(movie: &MovieInfo) {
     println!("Title: {}", movie.title);
     println!("Year: {}", movie.year);
     println!("Price: ${:.2}", movie.price);
     println!("Last Updated: {}", movie.timestamp);
+    if let Some(overview) = &movie.overview {
+        println!("Overview: {}", overview);
+    }
+    if let Some(genres) = &movie.genres {
+        println!("Genres: {}", genres.join(", "));
+    }
+    if let Some(runtime) = movie.runtime {
+        println!("Runtime: {} min", runtime);
+    }
 }
 
 /// Prompts the user to input movie details (title, year, price).
 /// Returns a MovieInfo struct with the entered data and current timestamp.
-pub fn input_movie() -> MovieInfo {
+/// `config.mode` controls whether TMDB enrichment may reach the network; in
+/// `AppMode::Offline` it is served from `cache` only. `config.last_tmdb_query`
+/// is updated with the title looked up, if any.
+pub fn input_movie(config: &mut AppConfig, cache: &mut MetadataCache) -> MovieInfo {
     let mut title = String::new();
     let mut year = String::new();
     let mut price = String::new();
@@ -65,65 +94,143 @@ pub fn input_movie() -> MovieInfo {
     io::stdin().read_line(&mut price).expect("Failed to read price");
     let price: f64 = price.trim().parse().unwrap_or(0.0);
 
-    MovieInfo {
+    let mut movie = MovieInfo {
         timestamp: get_current_timestamp(),
         title,
         year,
         price,
+        overview: None,
+        genres: None,
+        runtime: None,
+        tmdb_id: None,
+        poster_path: None,
+    };
+
+    print!("Fetch details from TMDB? (y/n): ");
+    io::stdout().flush().unwrap();
+    let mut fetch_choice = String::new();
+    io::stdin().read_line(&mut fetch_choice).expect("Failed to read choice");
+    if fetch_choice.trim().eq_ignore_ascii_case("y") {
+        offer_tmdb_enrichment(&mut movie, config, cache);
     }
+
+    movie
 }
 
-/// Saves the list of movies to a file in the "MovieData" directory.
-/// Movies are saved in JSON format for better data integrity and readability.
-/// Prints an error message if the file cannot be created or written.
-pub fn save_movies(movies: &[MovieInfo], filename: &str) {
-    ensure_movie_directory_exists();
-    let path = Path::new(filename);
-    
-    // Convert movies to pretty-printed JSON
-    let json_data = match serde_json::to_string_pretty(movies) {
-        Ok(data) => data,
+/// Looks `movie` up, lets the user pick a candidate, and merges the result
+/// in. In `AppMode::Offline` this only consults `cache` and never touches
+/// the network; in `AppMode::Online` any failure (missing API key, network
+/// error, no matches) falls back to the manually entered data with a
+/// message explaining why. `config.last_tmdb_query` is only updated when a
+/// search is actually sent to TMDB.
+fn offer_tmdb_enrichment(movie: &mut MovieInfo, config: &mut AppConfig, cache: &mut MetadataCache) {
+    if config.mode == AppMode::Offline {
+        match cache.find_by_title(&movie.title) {
+            Some(details) => {
+                movie.apply_enrichment(details.clone());
+                println!("Merged cached TMDB details into \"{}\" (offline mode).", movie.title);
+            }
+            None => println!("No cached TMDB details for \"{}\" (offline mode).", movie.title),
+        }
+        return;
+    }
+
+    let client = match TmdbClient::from_env() {
+        Ok(client) => client,
         Err(e) => {
-            eprintln!("Failed to convert movies to JSON: {}", e);
+            println!("Skipping TMDB lookup: {}", e);
             return;
         }
     };
-    
-    // Write JSON data to file
-    if let Err(e) = fs::write(path, json_data) {
-        eprintln!("Failed to save movies to {}: {}", filename, e);
-    } else {
-        println!("Movies saved successfully to {}", filename);
-    }
-}
 
-/// Loads movies from a file in the "MovieData" directory.
-/// Reads JSON formatted data and parses it into MovieInfo structs.
-/// Returns a vector of loaded movies. If the file does not exist, returns an empty vector.
-pub fn load_movies(filename: &str) -> Vec<MovieInfo> {
-    let path = Path::new(filename);
-    
-    // Check if file exists first
-    if !path.exists() {
-        return Vec::new();
-    }
-    
-    // Read the file content
-    let data = match fs::read_to_string(path) {
-        Ok(content) => content,
+    let runtime = match tokio::runtime::Runtime::new() {
+        Ok(rt) => rt,
         Err(e) => {
-            eprintln!("Warning: Could not read file {}: {}", filename, e);
-            return Vec::new();
+            println!("Skipping TMDB lookup: could not start async runtime: {}", e);
+            return;
         }
     };
-    
-    // Parse JSON data into MovieInfo objects
-    match serde_json::from_str(&data) {
-        Ok(movies) => movies,
+
+    config.last_tmdb_query = Some(movie.title.clone());
+    let candidates = match runtime.block_on(client.search(&movie.title, movie.year)) {
+        Ok(candidates) => candidates,
         Err(e) => {
-            eprintln!("Warning: Failed to parse JSON data from {}: {}", filename, e);
-            Vec::new()
+            println!("Skipping TMDB lookup: {}", e);
+            return;
+        }
+    };
+
+    println!("\n=== TMDB Matches ===");
+    for (i, candidate) in candidates.iter().enumerate() {
+        println!(
+            "{}. {} ({})",
+            i + 1,
+            candidate.title,
+            if candidate.release_date.is_empty() {
+                "unknown date"
+            } else {
+                candidate.release_date.as_str()
+            }
+        );
+    }
+    print!("Pick a match (or 0 to skip): ");
+    io::stdout().flush().unwrap();
+
+    let mut choice = String::new();
+    io::stdin().read_line(&mut choice).expect("Failed to read choice");
+    let idx: usize = match choice.trim().parse() {
+        Ok(0) | Err(_) => {
+            println!("Skipped TMDB enrichment.");
+            return;
+        }
+        Ok(n) => n - 1,
+    };
+    let Some(candidate) = candidates.get(idx) else {
+        println!("Invalid selection, skipping TMDB enrichment.");
+        return;
+    };
+
+    match runtime.block_on(client.fetch_details(candidate)) {
+        Ok(details) => {
+            cache.insert(details.clone());
+            movie.apply_enrichment(details);
+            println!("Merged TMDB details into \"{}\".", movie.title);
         }
+        Err(e) => println!("Skipping TMDB lookup: {}", e),
+    }
+}
+
+/// Saves the list of movies to `path` as pretty-printed JSON.
+/// The parent directory is expected to already exist (see `paths::movie_data_file`).
+pub fn save_movies(movies: &[MovieInfo], path: &Path) -> Result<(), MovieError> {
+    let json_data = serde_json::to_string_pretty(movies)?;
+    fs::write(path, json_data)?;
+    println!("Movies saved successfully to {}", path.display());
+    Ok(())
+}
+
+/// Loads movies from `path`.
+/// Returns an empty vector if the file doesn't exist yet (first run), but
+/// surfaces a `MovieError::InvalidInput` naming the path if the file exists
+/// and is corrupt, so callers can tell "nothing saved yet" apart from "data
+/// got mangled".
+pub fn load_movies(path: &Path) -> Result<Vec<MovieInfo>, MovieError> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let data = fs::read_to_string(path)?;
+    serde_json::from_str(&data)
+        .map_err(|e| MovieError::InvalidInput(format!("corrupt JSON in {}: {}", path.display(), e)))
+}
+
+/// Prints a heading followed by each movie's details, separated by dashes.
+/// Shared by `display_all_movies` and `display_matches` so the two don't drift.
+fn display_movie_list<'a>(heading: &str, movies: impl IntoIterator<Item = &'a MovieInfo>) {
+    println!("{}", heading);
+    for movie in movies {
+        display_movie(movie);
+        println!("---------------------");
     }
 }
 
@@ -134,11 +241,17 @@ pub fn display_all_movies(movies: &[MovieInfo]) {
         println!("No movies in database.");
         return;
     }
-    println!("\n=== Movie Database ===");
-    for movie in movies {
-        display_movie(movie);
-        println!("---------------------");
+    display_movie_list("\n=== Movie Database ===", movies);
+}
+
+/// Displays a set of search results using the same formatting as
+/// `display_all_movies`.
+pub fn display_matches(movies: &[&MovieInfo]) {
+    if movies.is_empty() {
+        println!("No movies match that query.");
+        return;
     }
+    display_movie_list("\n=== Search Results ===", movies.iter().copied());
 }
 
 /// Prompts the user to delete a movie by its index.
@@ -170,10 +283,15 @@ pub fn delete_movie(movies: &mut Vec<MovieInfo>) {
 
 /// Main Movie Database function.
 /// Handles the user interface loop for adding, viewing, and saving movies.
-/// Returns Ok(0) on normal exit, or Err(String) on error.
-pub fn run_movie_db() -> Result<i32, String> {
-    let filename = "MovieData/movies.json"; // Changed from .txt to .json
-    let mut movies = load_movies(filename);
+/// Returns Ok(0) on normal exit, or Err(MovieError) if loading/saving fails.
+pub fn run_movie_db() -> Result<i32, MovieError> {
+    let filename = crate::paths::movie_data_file()?;
+    let mut movies = load_movies(&filename)?;
+
+    let config_path = crate::paths::config_file()?;
+    let mut config = crate::config::AppConfig::load(&config_path)?;
+    let cache_path = crate::paths::metadata_cache_file()?;
+    let mut cache = MetadataCache::load(&cache_path)?;
 
     loop {
         // Display menu options
@@ -182,6 +300,9 @@ pub fn run_movie_db() -> Result<i32, String> {
         println!("2. View all movies");
         println!("3. Delete a movie");
         println!("4. Save & Exit");
+        println!("5. Search movies");
+        println!("6. Toggle online/offline mode (currently {})", config.mode);
+        println!("7. Download posters");
         print!("Choice: ");
         io::stdout().flush().unwrap();
 
@@ -193,8 +314,9 @@ pub fn run_movie_db() -> Result<i32, String> {
         match choice {
             "1" => {
                 // Add a new movie
-                let movie = input_movie();
+                let movie = input_movie(&mut config, &mut cache);
                 movies.push(movie);
+                cache.save(&cache_path)?;
             }
             "2" => {
                 // Display all movies
@@ -206,12 +328,123 @@ pub fn run_movie_db() -> Result<i32, String> {
             }
             "4" => {
                 // Save movies and exit
-                save_movies(&movies, filename);
+                save_movies(&movies, &filename)?;
+                config.save(&config_path)?;
+                cache.save(&cache_path)?;
                 println!("Data saved. Goodbye!");
                 break;
             }
+            "5" => {
+                // Search movies with a query line
+                print!("Query (e.g. \"title:matrix year:1999-2003 price:<15\"): ");
+                io::stdout().flush().unwrap();
+                let mut query_line = String::new();
+                io::stdin().read_line(&mut query_line).expect("Failed to read query");
+                let query = crate::query::parse_query(query_line.trim());
+                let matches = crate::query::filter_movies(&movies, &query);
+                display_matches(&matches);
+            }
+            "6" => {
+                // Toggle online/offline mode
+                let new_mode = match config.mode {
+                    AppMode::Online => AppMode::Offline,
+                    AppMode::Offline => AppMode::Online,
+                };
+                config.mode = new_mode;
+                println!("Switched to {} mode.", config.mode);
+                if new_mode == AppMode::Online {
+                    retry_pending_enrichment(&mut movies, &mut cache, &mut config);
+                    cache.save(&cache_path)?;
+                }
+                config.save(&config_path)?;
+            }
+            "7" => {
+                // Download posters for movies that have one but no local file yet
+                download_missing_posters(&movies);
+            }
             _ => println!("Invalid choice. Please try again."),
         }
     }
     Ok(0)
+}
+
+/// Downloads posters for every movie that has a `poster_path` but no local
+/// file yet, skipping ones already present.
+fn download_missing_posters(movies: &[MovieInfo]) {
+    let data_dir = match crate::paths::data_dir() {
+        Ok(dir) => dir,
+        Err(e) => {
+            println!("Skipping poster downloads: {}", e);
+            return;
+        }
+    };
+
+    for movie in movies {
+        let (Some(_), Some(tmdb_id)) = (&movie.poster_path, movie.tmdb_id) else {
+            continue;
+        };
+        let dest = data_dir.join("posters").join(format!("{}.jpg", tmdb_id));
+        if dest.exists() {
+            continue;
+        }
+        println!("Downloading poster for \"{}\"...", movie.title);
+        match crate::poster::download_poster(movie, &data_dir) {
+            Ok(path) => println!("Saved poster to {}", path.display()),
+            Err(e) => println!("Failed to download poster for \"{}\": {}", movie.title, e),
+        }
+    }
+}
+
+/// Re-runs TMDB enrichment for every movie that was never successfully
+/// enriched (no `tmdb_id`), called after switching from offline back to
+/// online mode. Uses the top search match automatically rather than
+/// prompting, since this can retry many movies at once.
+fn retry_pending_enrichment(movies: &mut [MovieInfo], cache: &mut MetadataCache, config: &mut AppConfig) {
+    let pending: Vec<usize> = movies
+        .iter()
+        .enumerate()
+        .filter(|(_, m)| m.tmdb_id.is_none())
+        .map(|(i, _)| i)
+        .collect();
+    if pending.is_empty() {
+        return;
+    }
+
+    let client = match TmdbClient::from_env() {
+        Ok(client) => client,
+        Err(e) => {
+            println!("Skipping retry of pending enrichment: {}", e);
+            return;
+        }
+    };
+    let runtime = match tokio::runtime::Runtime::new() {
+        Ok(rt) => rt,
+        Err(e) => {
+            println!("Skipping retry of pending enrichment: could not start async runtime: {}", e);
+            return;
+        }
+    };
+
+    for idx in pending {
+        let movie = &mut movies[idx];
+        config.last_tmdb_query = Some(movie.title.clone());
+        let candidates = match runtime.block_on(client.search(&movie.title, movie.year)) {
+            Ok(candidates) => candidates,
+            Err(e) => {
+                println!("Still couldn't enrich \"{}\": {}", movie.title, e);
+                continue;
+            }
+        };
+        let Some(top_match) = candidates.first() else {
+            continue;
+        };
+        match runtime.block_on(client.fetch_details(top_match)) {
+            Ok(details) => {
+                cache.insert(details.clone());
+                movie.apply_enrichment(details);
+                println!("Enriched \"{}\" from TMDB.", movie.title);
+            }
+            Err(e) => println!("Still couldn't enrich \"{}\": {}", movie.title, e),
+        }
+    }
 }
\ No newline at end of file